@@ -113,11 +113,27 @@
 
 extern crate suffix;
 extern crate itertools;
+extern crate fst;
+extern crate levenshtein_automata;
+extern crate memmap;
+extern crate byteorder;
+extern crate unicode_normalization;
 
 use suffix::SuffixTable;
-use std::collections::{BTreeMap};
+use fst::{Set, IntoStreamer, Streamer};
+use levenshtein_automata::LevenshteinAutomatonBuilder;
+use memmap::{Mmap, Protection};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::collections::Bound::{Included, Unbounded};
+use std::fs::File;
+use std::io::{self, BufWriter, Cursor, Write};
 use std::iter::FromIterator;
+use std::path::Path;
+use std::str;
 
 
 /// Every value that is inserted into the lookup table must be assigned to a bucket.
@@ -143,30 +159,121 @@ pub trait Lookup {
     fn bucket(&self) -> Bucket;
 }
 
+/// Folds the searchable text before it is indexed and looked up.
+/// The same `Normalizer` is applied at build time (in `from_iter_normalized`)
+/// and at query time, so that e.g. a lower-cased, diacritic-stripped query can
+/// match differently-cased or accented text.
+/// The default `Normalizer` leaves the text untouched, which is what the plain
+/// `from_iter` uses to keep the historical case-sensitive behavior.
+#[derive(Debug, Clone)]
+pub struct Normalizer {
+    lowercase: bool,
+    strip_diacritics: bool
+}
+
+impl Normalizer {
+
+    /// Creates a `Normalizer` that optionally lower-cases the text and
+    /// optionally strips diacritics via NFD decomposition, dropping the
+    /// resulting combining marks.
+    pub fn new(lowercase: bool, strip_diacritics: bool) -> Normalizer {
+        Normalizer{lowercase: lowercase, strip_diacritics: strip_diacritics}
+    }
+
+    fn is_identity(&self) -> bool {
+        !self.lowercase && !self.strip_diacritics
+    }
+
+    fn normalize<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if self.is_identity() {
+            return Cow::Borrowed(text);
+        }
+        let lowered = if self.lowercase {
+            text.to_lowercase()
+        } else {
+            text.to_string()
+        };
+        if self.strip_diacritics {
+            Cow::Owned(lowered.nfd().filter(|c| !is_combining_mark(*c)).collect())
+        } else {
+            Cow::Owned(lowered)
+        }
+    }
+}
+
+impl Default for Normalizer {
+    fn default() -> Normalizer {
+        Normalizer{lowercase: false, strip_diacritics: false}
+    }
+}
+
+/// The byte range of a single query match within an entry's `searchable_text`.
+/// `start` is the byte offset of the match and `len` its length in bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchBound {
+    /// Byte offset of the match within the entry's `searchable_text`.
+    pub start: usize,
+    /// Length of the match in bytes.
+    pub len: usize
+}
+
 /// This is the actual `LookupTable` that creates the in memory data structure and uses it to perform the lookups.
 /// It implements the `FromIterator` trait and its `from_iter(..)` method.
 /// To create a new `LookupTable` instance, you first have to create an Iterator over some `Lookup` items.
 /// Having that iterator, you can call `LookupTable::from_iter(myLookupItemIterator)``.
 pub struct LookupTable<'a, V: 'a>  where V: Lookup{
     suffix_table_map: BTreeMap<Bucket, SuffixTable<'a,'a>>,
-    position_map: BTreeMap<(Bucket, TextPosition), V>
+    position_map: BTreeMap<(Bucket, TextPosition), V>,
+    word_set_map: BTreeMap<Bucket, Set>,
+    posting_map: BTreeMap<(Bucket, String), Vec<TextPosition>>,
+    dfa_builders: [LevenshteinAutomatonBuilder; 3],
+    normalizer: Normalizer
 }
 
 impl <'a, A: Lookup>FromIterator<A> for LookupTable<'a, A>{
 
     /// Creates a `LookupTable` from the given Iterator
     fn from_iter<T>(iterator: T) -> Self where T: IntoIterator<Item=A>{
-        let mut text_map: BTreeMap<Bucket, String> = BTreeMap::new();
-        let mut position_map: BTreeMap<(Bucket, TextPosition), A> = BTreeMap::new();
+        LookupTable::from_iter_normalized(iterator, Normalizer::default())
+    }
+}
 
-        for value in iterator {
-            let mut text = text_map.entry(value.bucket()).or_insert_with(String::new);
-            let pos: TextPosition = text.len();
+impl <'a, V>LookupTable<'a, V> where V: Lookup{
 
-            text.push_str(&value.searchable_text().as_str());
-            text.push_str(SEPARATOR);
+    /// Creates a `LookupTable` from the given Iterator, folding all searchable
+    /// text through `normalizer` before it is indexed.
+    /// The original `searchable_text` is still stored on the entry, so the
+    /// returned `&V` keeps exposing the display form; only the searchable index
+    /// is folded.
+    /// The same `normalizer` is applied to every query, so lookups must be
+    /// performed through the `find*` methods of this table to stay consistent.
+    pub fn from_iter_normalized<T>(iterator: T, normalizer: Normalizer) -> Self where T: IntoIterator<Item=V>{
+        let mut text_map: BTreeMap<Bucket, String> = BTreeMap::new();
+        let mut position_map: BTreeMap<(Bucket, TextPosition), V> = BTreeMap::new();
+        let mut posting_map: BTreeMap<(Bucket, String), Vec<TextPosition>> = BTreeMap::new();
 
-            position_map.insert((value.bucket(), pos), value);
+        for value in iterator {
+            let bucket = value.bucket();
+            let original_text = value.searchable_text();
+            let searchable_text = normalizer.normalize(&original_text);
+            let pos: TextPosition = {
+                let mut text = text_map.entry(bucket).or_insert_with(String::new);
+                let pos = text.len();
+                text.push_str(&searchable_text);
+                text.push_str(SEPARATOR);
+                pos
+            };
+
+            // Index every distinct word of the entry so that `find_fuzzy` can
+            // look matches up through a per-bucket word FST.
+            for word in searchable_text.split_whitespace() {
+                let postings = posting_map.entry((bucket, word.to_string())).or_insert_with(Vec::new);
+                if postings.last() != Some(&pos) {
+                    postings.push(pos);
+                }
+            }
+
+            position_map.insert((bucket, pos), value);
         }
 
         let mut suffix_table_map: BTreeMap<Bucket, SuffixTable> = BTreeMap::new();
@@ -174,16 +281,43 @@ impl <'a, A: Lookup>FromIterator<A> for LookupTable<'a, A>{
             suffix_table_map.insert(bucket, SuffixTable::new(text));
         }
 
-        LookupTable{suffix_table_map: suffix_table_map, position_map: position_map}
-    }
-}
+        // An `fst::Set` requires its keys to be inserted in lexicographic order,
+        // so collect the distinct words per bucket into a `BTreeSet` first.
+        let mut words_per_bucket: BTreeMap<Bucket, BTreeSet<String>> = BTreeMap::new();
+        for &(bucket, ref word) in posting_map.keys() {
+            words_per_bucket.entry(bucket).or_insert_with(BTreeSet::new).insert(word.clone());
+        }
+        let mut word_set_map: BTreeMap<Bucket, Set> = BTreeMap::new();
+        for (bucket, words) in words_per_bucket.into_iter() {
+            let set = Set::from_iter(words).expect("word list is sorted and deduplicated");
+            word_set_map.insert(bucket, set);
+        }
 
-impl <'a, V>LookupTable<'a, V> where V: Lookup{
+        let dfa_builders = [
+            LevenshteinAutomatonBuilder::new(0, true),
+            LevenshteinAutomatonBuilder::new(1, true),
+            LevenshteinAutomatonBuilder::new(2, true)
+        ];
+
+        LookupTable{
+            suffix_table_map: suffix_table_map,
+            position_map: position_map,
+            word_set_map: word_set_map,
+            posting_map: posting_map,
+            dfa_builders: dfa_builders,
+            normalizer: normalizer
+        }
+    }
 
     fn get_value_for_position(&self, bucket: Bucket, text_position: TextPosition) -> &V{
+        let key = self.get_entry_key_for_position(bucket, text_position);
+        &self.position_map[&key]
+    }
+
+    fn get_entry_key_for_position(&self, bucket: Bucket, text_position: TextPosition) -> (Bucket, TextPosition){
         if let Some(value) = self.position_map.range((Unbounded, Included(&(bucket,(text_position as usize))))).rev().next() {
-            let (&(_, _), value) = value;
-            value
+            let (&(entry_bucket, entry_position), _) = value;
+            (entry_bucket, entry_position)
         }else {
             panic!("Could not find at least one value in position map.
                     This must be a bug! Please report it on https://github.com/forgemo/ironstorm_lookup/issues");
@@ -194,8 +328,10 @@ impl <'a, V>LookupTable<'a, V> where V: Lookup{
     /// If the `search_text` is found multiple times for the same entry, the entry will also be returned multiple times.
     /// If no matches are found, the Iterator will immediately start returning `None`.
     /// Entries in lower buckets will be returned before entries in higher buckets.
-    /// The method is case sensitive.
+    /// The method is case sensitive, unless the table was built through
+    /// `from_iter_normalized` with a folding `Normalizer`.
     pub fn find(&'a self, search_text: &'a str) -> Box<Iterator<Item=&V> + 'a> {
+        let search_text = self.normalizer.normalize(search_text);
         let result_iter = self.suffix_table_map.iter()
         .flat_map(move |(bucket, suffix_table)|{
             suffix_table.positions(&search_text).iter().map(move |text_position|(bucket, text_position))
@@ -204,6 +340,163 @@ impl <'a, V>LookupTable<'a, V> where V: Lookup{
         return Box::new(result_iter);
     }
 
+    /// Searches for `Lookup` entries whose `searchable_text` contains *every*
+    /// whitespace separated term of the `query`, in any position or order.
+    /// Each term is resolved to the set of entries that contain it via the
+    /// existing suffix tables; those sets are then intersected, starting with
+    /// the smallest one to minimize work.
+    /// Unlike `find`, every matching entry is returned exactly once, in
+    /// ascending bucket order.
+    /// The method is case sensitive, unless the table was built through
+    /// `from_iter_normalized` with a folding `Normalizer`.
+    pub fn find_all(&'a self, query: &'a str) -> Box<Iterator<Item=&V> + 'a> {
+        let query = self.normalizer.normalize(query);
+        let terms: Vec<&str> = query.split_whitespace().collect();
+        if terms.is_empty() {
+            return Box::new(Vec::new().into_iter());
+        }
+
+        let mut term_sets: Vec<HashSet<(Bucket, TextPosition)>> = Vec::with_capacity(terms.len());
+        for term in &terms {
+            let mut set: HashSet<(Bucket, TextPosition)> = HashSet::new();
+            for (bucket, suffix_table) in self.suffix_table_map.iter() {
+                for position in suffix_table.positions(term).iter() {
+                    set.insert(self.get_entry_key_for_position(*bucket, *position as usize));
+                }
+            }
+            if set.is_empty() {
+                return Box::new(Vec::new().into_iter());
+            }
+            term_sets.push(set);
+        }
+
+        term_sets.sort_by_key(|set| set.len());
+        let (smallest, rest) = term_sets.split_first().expect("at least one term");
+        let mut result_keys: Vec<(Bucket, TextPosition)> = smallest.iter()
+            .filter(|key| rest.iter().all(|set| set.contains(key)))
+            .cloned()
+            .collect();
+        result_keys.sort();
+
+        let result_iter = result_keys.into_iter()
+            .map(move |(bucket, position)| self.get_value_for_position(bucket, position));
+        return Box::new(result_iter);
+    }
+
+    /// Searches for `Lookup` entries that contain a word within `max_distance`
+    /// edits (insertions, deletions, substitutions or transpositions) of the
+    /// given `term`.
+    /// The actual edit budget is the smaller of `max_distance` and a budget
+    /// derived from the term length (0 for up to 4 characters, 1 for up to 8,
+    /// 2 otherwise), capped at 2 because only those three automata are cached.
+    /// Matches are returned in ascending bucket order, preserving the coarse
+    /// ranking of `find`.
+    /// When `distinct` is set, each entry is returned at most once even if it
+    /// matches through several words; otherwise an entry is yielded once per
+    /// distinct word of it that matches (a word repeated within one entry
+    /// counts once, unlike the per-occurrence multiplicity of `find`).
+    /// The method is case sensitive, unless the table was built through
+    /// `from_iter_normalized` with a folding `Normalizer`.
+    pub fn find_fuzzy(&'a self, term: &'a str, max_distance: u8, distinct: bool) -> Box<Iterator<Item=&V> + 'a> {
+        let term = self.normalizer.normalize(term);
+        let length_budget = match term.chars().count() {
+            0...4 => 0,
+            5...8 => 1,
+            _     => 2
+        };
+        let distance = ::std::cmp::min(::std::cmp::min(max_distance, length_budget), 2);
+        let dfa = self.dfa_builders[distance as usize].build_dfa(&term);
+
+        let mut results: Vec<&V> = Vec::new();
+        let mut seen: HashSet<(Bucket, TextPosition)> = HashSet::new();
+        for (bucket, word_set) in self.word_set_map.iter() {
+            let mut stream = word_set.search(&dfa).into_stream();
+            while let Some(word) = stream.next() {
+                let word = String::from_utf8_lossy(word).into_owned();
+                if let Some(positions) = self.posting_map.get(&(*bucket, word)) {
+                    for position in positions {
+                        if distinct && !seen.insert((*bucket, *position)) {
+                            continue;
+                        }
+                        results.push(self.get_value_for_position(*bucket, *position));
+                    }
+                }
+            }
+        }
+        return Box::new(results.into_iter());
+    }
+
+    /// Searches like `find`, but additionally reports where the `query` hit
+    /// within each entry.
+    /// Each matching entry is returned once, together with the list of
+    /// `MatchBound`s describing the byte ranges (relative to that entry's
+    /// `searchable_text`) at which the `query` was found, sorted by offset.
+    /// This is meant to drive highlighting in type-ahead UIs.
+    /// Entries are returned in ascending bucket order.
+    /// The reported offsets index the entry's original `searchable_text`, which
+    /// only coincides with the searchable index when no folding takes place.
+    /// This method therefore requires an identity `Normalizer` and panics when
+    /// called on a table built through `from_iter_normalized` with a folding
+    /// one; use `find` for normalized tables.
+    pub fn find_with_matches(&'a self, query: &'a str) -> Box<Iterator<Item=(&V, Vec<MatchBound>)> + 'a> {
+        assert!(self.normalizer.is_identity(),
+            "find_with_matches is only valid on identity-normalized tables, \
+             because its MatchBound offsets index the original searchable_text");
+        let query = self.normalizer.normalize(query);
+        let mut bounds: BTreeMap<(Bucket, TextPosition), Vec<MatchBound>> = BTreeMap::new();
+        for (bucket, suffix_table) in self.suffix_table_map.iter() {
+            for position in suffix_table.positions(&query).iter() {
+                let key = self.get_entry_key_for_position(*bucket, *position as usize);
+                let start = (*position as usize) - key.1;
+                bounds.entry(key).or_insert_with(Vec::new)
+                    .push(MatchBound{start: start, len: query.len()});
+            }
+        }
+        let results: Vec<(&V, Vec<MatchBound>)> = bounds.into_iter()
+            .map(move |(key, mut entry_bounds)| {
+                entry_bounds.sort_by_key(|bound| bound.start);
+                (self.get_value_for_position(key.0, key.1), entry_bounds)
+            })
+            .collect();
+        return Box::new(results.into_iter());
+    }
+
+    /// Searches like `find`, but returns only a bounded page of the results.
+    /// `offset` entries are skipped and at most `limit` entries are returned,
+    /// preserving the ascending bucket order of `find`.
+    /// When `distinct` is set, repeated emissions of an already-seen entry
+    /// (identified by its `(Bucket, TextPosition)` key) are skipped, so each
+    /// entry is counted and returned at most once.
+    /// The underlying suffix tables are only iterated until the page is full,
+    /// which makes this cheap for the common "first N of millions" type-ahead
+    /// use case.
+    pub fn find_page(&'a self, query: &'a str, offset: usize, limit: usize, distinct: bool) -> Box<Iterator<Item=&V> + 'a> {
+        let mut results: Vec<&V> = Vec::new();
+        if limit == 0 {
+            return Box::new(results.into_iter());
+        }
+        let query = self.normalizer.normalize(query);
+        let mut seen: HashSet<(Bucket, TextPosition)> = HashSet::new();
+        let mut skipped = 0;
+        'outer: for (bucket, suffix_table) in self.suffix_table_map.iter() {
+            for position in suffix_table.positions(&query).iter() {
+                let key = self.get_entry_key_for_position(*bucket, *position as usize);
+                if distinct && !seen.insert(key) {
+                    continue;
+                }
+                if skipped < offset {
+                    skipped += 1;
+                    continue;
+                }
+                results.push(self.get_value_for_position(key.0, key.1));
+                if results.len() >= limit {
+                    break 'outer;
+                }
+            }
+        }
+        return Box::new(results.into_iter());
+    }
+
     /// Returns the number of values for this `LookupTable`
     pub fn len(&self) -> usize {
         self.position_map.len()
@@ -214,14 +507,160 @@ impl <'a, V>LookupTable<'a, V> where V: Lookup{
         self.suffix_table_map.len()
     }
 
+    /// Serializes the per-bucket concatenated text, the corresponding suffix
+    /// arrays and the entry start positions into a single file at `path`.
+    /// The file can later be reopened with `MmapLookupTable::open_mmap` so that
+    /// the precomputed table is memory mapped instead of being rebuilt.
+    /// Everything is written as fixed-width little-endian values, one section
+    /// per bucket, in ascending bucket order.
+    /// The active `Normalizer` is stored in the header so that the reopened
+    /// `MmapLookupTable` folds queries the same way the index was folded.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_u8(self.normalizer.lowercase as u8)?;
+        file.write_u8(self.normalizer.strip_diacritics as u8)?;
+        file.write_u64::<LittleEndian>(self.suffix_table_map.len() as u64)?;
+        for (bucket, suffix_table) in self.suffix_table_map.iter() {
+            let text = suffix_table.text();
+            let table = suffix_table.table();
+            let positions: Vec<u64> = self.position_map.keys()
+                .filter(|&&(entry_bucket, _)| entry_bucket == *bucket)
+                .map(|&(_, position)| position as u64)
+                .collect();
+
+            file.write_u64::<LittleEndian>(*bucket as u64)?;
+            file.write_u64::<LittleEndian>(text.len() as u64)?;
+            file.write_all(text.as_bytes())?;
+            file.write_u64::<LittleEndian>(table.len() as u64)?;
+            for &suffix in table {
+                file.write_u32::<LittleEndian>(suffix)?;
+            }
+            file.write_u64::<LittleEndian>(positions.len() as u64)?;
+            for &position in &positions {
+                file.write_u64::<LittleEndian>(position)?;
+            }
+        }
+        file.flush()
+    }
+
+}
+
+/// A read-only `LookupTable` backed by a memory mapped file produced by
+/// `LookupTable::save`.
+/// The concatenated per-bucket text stays in the mapped region and is paged in
+/// by the OS on demand. The suffix arrays and entry positions are not: they are
+/// decoded into memory when the file is opened, so opening time and resident
+/// memory grow with the size of the suffix arrays (four bytes per text byte).
+/// Because the original `Lookup` values can not be reconstructed from disk,
+/// `find` yields the stored (and, if a `Normalizer` was used, already folded)
+/// `searchable_text` slices rather than `&V`.
+/// The `Normalizer` the table was built with is restored from the file header
+/// so that `find` folds queries the same way the index was folded.
+pub struct MmapLookupTable {
+    mmap: Mmap,
+    sections: Vec<MmapSection>,
+    normalizer: Normalizer
+}
+
+struct MmapSection {
+    text_start: usize,
+    text_len: usize,
+    suffix: Vec<u32>,
+    positions: Vec<TextPosition>
+}
+
+impl MmapSection {
+
+    /// Resolves a suffix match `position` to the byte range of its owning entry
+    /// within the bucket text.
+    fn entry_range(&self, text: &str, position: usize) -> (usize, usize) {
+        let start = match self.positions.binary_search(&position) {
+            Ok(index) => self.positions[index],
+            Err(0) => 0,
+            Err(index) => self.positions[index - 1]
+        };
+        let end = match text[start..].find(SEPARATOR) {
+            Some(offset) => start + offset,
+            None => text.len()
+        };
+        (start, end)
+    }
+}
+
+impl MmapLookupTable {
+
+    /// Opens a file previously written by `LookupTable::save` and maps it into
+    /// memory.
+    /// The suffix arrays and entry positions are decoded eagerly, while the
+    /// text stays borrowed from the mapped region.
+    pub fn open_mmap<P: AsRef<Path>>(path: P) -> io::Result<MmapLookupTable> {
+        let mmap = Mmap::open_path(path, Protection::Read)?;
+        let mut sections = Vec::new();
+        let normalizer;
+        {
+            let bytes = unsafe { mmap.as_slice() };
+            let mut cursor = Cursor::new(bytes);
+            let lowercase = cursor.read_u8()? != 0;
+            let strip_diacritics = cursor.read_u8()? != 0;
+            normalizer = Normalizer::new(lowercase, strip_diacritics);
+            let bucket_count = cursor.read_u64::<LittleEndian>()?;
+            for _ in 0..bucket_count {
+                let _bucket = cursor.read_u64::<LittleEndian>()?;
+                let text_len = cursor.read_u64::<LittleEndian>()? as usize;
+                let text_start = cursor.position() as usize;
+                cursor.set_position((text_start + text_len) as u64);
+
+                let suffix_len = cursor.read_u64::<LittleEndian>()? as usize;
+                let mut suffix = Vec::with_capacity(suffix_len);
+                for _ in 0..suffix_len {
+                    suffix.push(cursor.read_u32::<LittleEndian>()?);
+                }
+
+                let positions_len = cursor.read_u64::<LittleEndian>()? as usize;
+                let mut positions = Vec::with_capacity(positions_len);
+                for _ in 0..positions_len {
+                    positions.push(cursor.read_u64::<LittleEndian>()? as TextPosition);
+                }
+
+                sections.push(MmapSection{
+                    text_start: text_start,
+                    text_len: text_len,
+                    suffix: suffix,
+                    positions: positions
+                });
+            }
+        }
+        Ok(MmapLookupTable{mmap: mmap, sections: sections, normalizer: normalizer})
+    }
+
+    /// Searches the mapped table exactly like `LookupTable::find`, but yields
+    /// the matching entries' `searchable_text` slices borrowed from the mapped
+    /// region.
+    pub fn find<'a>(&'a self, search_text: &'a str) -> Box<Iterator<Item=&'a str> + 'a> {
+        let search_text = self.normalizer.normalize(search_text);
+        let bytes = unsafe { self.mmap.as_slice() };
+        let mut results: Vec<&str> = Vec::new();
+        for section in &self.sections {
+            let text = unsafe {
+                str::from_utf8_unchecked(&bytes[section.text_start..section.text_start + section.text_len])
+            };
+            let suffix_table = SuffixTable::from_parts(Cow::Borrowed(text), Cow::Borrowed(&section.suffix[..]));
+            for position in suffix_table.positions(&search_text).iter() {
+                let (start, end) = section.entry_range(text, *position as usize);
+                results.push(&text[start..end]);
+            }
+        }
+        return Box::new(results.into_iter());
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
 
-    use {Lookup, LookupTable};
+    use {Lookup, LookupTable, MmapLookupTable, MatchBound, Normalizer};
     use std::iter::FromIterator;
+    use std::env;
 
     impl <'a> Lookup for &'a str {
         fn searchable_text(&self) -> String {
@@ -295,4 +734,81 @@ mod tests {
         let i = t.find("D");
         assert_eq!(0, i.count());
     }
+
+    #[test]
+    fn it_works_fuzzy() {
+        let strings = vec!["hello","hallo","world"];
+        let t = LookupTable::from_iter(strings.into_iter());
+        let mut matches = t.find_fuzzy("hello", 1, true).collect::<Vec<_>>();
+        matches.sort();
+        assert_eq!(vec![&"hallo", &"hello"], matches);
+    }
+
+    #[test]
+    fn it_works_find_all() {
+        let strings = vec!["space hero", "space opera", "super hero", "space hero movie"];
+        let t = LookupTable::from_iter(strings.into_iter());
+        let mut matches = t.find_all("space hero").collect::<Vec<_>>();
+        matches.sort();
+        assert_eq!(vec![&"space hero", &"space hero movie"], matches);
+    }
+
+    #[test]
+    fn it_works_save_and_mmap() {
+        let strings = vec!["ZZZ","ZZ","Z"];
+        let t = LookupTable::from_iter(strings.into_iter());
+        let mut path = env::temp_dir();
+        path.push("ironstorm_lookup_mmap_roundtrip.ilt");
+        t.save(&path).unwrap();
+
+        let mapped = MmapLookupTable::open_mmap(&path).unwrap();
+        let mut matches = mapped.find("Z").collect::<Vec<_>>();
+        matches.sort();
+        assert_eq!(vec!["Z", "ZZ", "ZZ", "ZZZ", "ZZZ", "ZZZ"], matches);
+    }
+
+    #[test]
+    fn it_works_find_page() {
+        let strings = vec!["ZZZ","ZZZ","A","ZZZ","B","ZZZ"];
+        let t = LookupTable::from_iter(strings.into_iter());
+        assert_eq!(4, t.find_page("Z", 0, 100, true).count());
+        assert_eq!(2, t.find_page("Z", 0, 2, true).count());
+        assert_eq!(3, t.find_page("Z", 1, 100, true).count());
+        // Without `distinct`, every match position is emitted.
+        assert_eq!(12, t.find_page("Z", 0, 100, false).count());
+    }
+
+    #[test]
+    fn it_works_find_with_matches() {
+        let strings = vec!["abcabc"];
+        let t = LookupTable::from_iter(strings.into_iter());
+        let results = t.find_with_matches("bc").collect::<Vec<_>>();
+        assert_eq!(1, results.len());
+        assert_eq!(&"abcabc", results[0].0);
+        assert_eq!(vec![MatchBound{start:1, len:2}, MatchBound{start:4, len:2}], results[0].1);
+    }
+
+    #[test]
+    fn it_works_normalized() {
+        let strings = vec!["Café", "Restaurant"];
+        let t = LookupTable::from_iter_normalized(strings.into_iter(), Normalizer::new(true, true));
+        // Both the folded and the accented query match, and the returned
+        // value keeps its original display form.
+        assert_eq!(vec![&"Café"], t.find("cafe").collect::<Vec<_>>());
+        assert_eq!(vec![&"Café"], t.find("Café").collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn it_works_normalized_save_and_mmap() {
+        let strings = vec!["Café"];
+        let t = LookupTable::from_iter_normalized(strings.into_iter(), Normalizer::new(true, false));
+        let mut path = env::temp_dir();
+        path.push("ironstorm_lookup_mmap_normalized_roundtrip.ilt");
+        t.save(&path).unwrap();
+
+        // The reopened table must fold queries the same way the index was
+        // folded, so a differently-cased query still matches.
+        let mapped = MmapLookupTable::open_mmap(&path).unwrap();
+        assert_eq!(vec!["café"], mapped.find("CAFÉ").collect::<Vec<_>>());
+    }
 }